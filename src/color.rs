@@ -1,4 +1,9 @@
+use std::env;
+use std::error::Error;
 use std::fmt;
+use std::str::FromStr;
+use std::sync::Once;
+use std::sync::atomic::{AtomicUsize, Ordering};
 
 use {Paint, Style};
 
@@ -94,6 +99,108 @@ impl Color {
         Style::new(self)
     }
 
+    /// Quantizes this color down to the set of colors supported by `level`,
+    /// returning a `Color` that renders faithfully on a terminal of that
+    /// capability. A `TrueColor` level is a no-op; `TwoFiftySix` collapses an
+    /// `RGB` color to a palette index by flooring each channel to its color
+    /// cube coordinate (or to the grayscale ramp); `Sixteen` collapses both
+    /// `RGB` and `Fixed` colors to the nearest of the 16 basic colors. Named
+    /// colors, `Default`, and `Unset` are never changed.
+    ///
+    /// ```rust
+    /// use yansi::{Color, ColorLevel};
+    ///
+    /// assert_eq!(Color::RGB(0, 0, 0).downgrade(ColorLevel::Sixteen), Color::Black);
+    /// ```
+    pub fn downgrade(self, level: ColorLevel) -> Color {
+        match level {
+            ColorLevel::TrueColor => self,
+            ColorLevel::TwoFiftySix => match self {
+                Color::RGB(r, g, b) => Color::Fixed(rgb_to_256(r, g, b)),
+                other => other,
+            },
+            ColorLevel::Sixteen => match self {
+                Color::RGB(r, g, b) => rgb_to_16(r, g, b),
+                Color::Fixed(n) => {
+                    let (r, g, b) = fixed_to_rgb(n);
+                    rgb_to_16(r, g, b)
+                }
+                other => other,
+            },
+        }
+    }
+
+    /// Parses a `Color` from a user-supplied string, accepting a named color
+    /// (`"red"`, `"bright-blue"`, `"default"`), a bare integer `"0"`..`"255"`
+    /// mapping to [`Fixed`](Color::Fixed), a comma- or space-separated triple
+    /// (`"0,128,255"`) mapping to [`RGB`](Color::RGB), or a `#rrggbb` hex
+    /// string. This is the inverse of the forms users pass on command lines and
+    /// in config files.
+    ///
+    /// ```rust
+    /// use yansi::Color;
+    ///
+    /// assert_eq!(Color::parse("bright-blue"), Ok(Color::BrightBlue));
+    /// assert_eq!(Color::parse("12"), Ok(Color::Fixed(12)));
+    /// assert_eq!(Color::parse("0,128,255"), Ok(Color::RGB(0, 128, 255)));
+    /// assert_eq!(Color::parse("#0080ff"), Ok(Color::RGB(0, 128, 255)));
+    /// ```
+    pub fn parse(s: &str) -> Result<Color, ParseColorError> {
+        s.parse()
+    }
+
+    /// Produces the sequence of `len` [`RGB`](Color::RGB) colors that linearly
+    /// interpolates from `start` to `end`, one color per character of a run of
+    /// text. Each channel of character `i` is `start + (end - start) * i / (len - 1)`
+    /// computed in `f32` and rounded. Non-`RGB` endpoints are first promoted to
+    /// their canonical RGB values.
+    ///
+    /// A `len` of `0` yields an empty sequence and a `len` of `1` yields just
+    /// `start` (promoted to RGB).
+    ///
+    /// ```rust
+    /// use yansi::Color;
+    ///
+    /// let colors = Color::gradient(Color::RGB(0, 0, 0), Color::RGB(0, 0, 10), 3);
+    /// assert_eq!(colors, vec![
+    ///     Color::RGB(0, 0, 0),
+    ///     Color::RGB(0, 0, 5),
+    ///     Color::RGB(0, 0, 10),
+    /// ]);
+    /// ```
+    pub fn gradient(start: Color, end: Color, len: usize) -> Vec<Color> {
+        let (sr, sg, sb) = start.rgb_value();
+        let (er, eg, eb) = end.rgb_value();
+
+        (0..len)
+            .map(|i| {
+                if len <= 1 {
+                    return Color::RGB(sr, sg, sb);
+                }
+
+                let t = i as f32 / (len - 1) as f32;
+                let lerp = |s: u8, e: u8| (s as f32 + (e as f32 - s as f32) * t).round() as u8;
+                Color::RGB(lerp(sr, er), lerp(sg, eg), lerp(sb, eb))
+            })
+            .collect()
+    }
+
+    /// Returns the canonical 24-bit RGB value of this color. Named colors map
+    /// through the standard ANSI palette, `Fixed` indices through the xterm
+    /// 256-color table, and `Default`/`Unset` to white (the typical foreground).
+    fn rgb_value(self) -> (u8, u8, u8) {
+        match self {
+            Color::RGB(r, g, b) => (r, g, b),
+            Color::Fixed(n) => fixed_to_rgb(n),
+            Color::Unset | Color::Default => (255, 255, 255),
+            named => PALETTE_16
+                .iter()
+                .find(|&&(color, _)| color == named)
+                .map(|&(_, rgb)| rgb)
+                .unwrap_or((255, 255, 255)),
+        }
+    }
+
     pub fn is_bright(&self) -> bool {
         match *self {
             Color::Unset
@@ -119,6 +226,40 @@ impl Color {
         }
     }
 
+    /// Returns the bright twin of this color, mapping `Red` to `BrightRed` and
+    /// so on. Colors that are already bright are returned unchanged, as are
+    /// `Fixed`, `RGB`, `Default`, and `Unset`.
+    pub fn to_bright(self) -> Color {
+        match self {
+            Color::Black => Color::BrightBlack,
+            Color::Red => Color::BrightRed,
+            Color::Green => Color::BrightGreen,
+            Color::Yellow => Color::BrightYellow,
+            Color::Blue => Color::BrightBlue,
+            Color::Magenta => Color::BrightMagenta,
+            Color::Cyan => Color::BrightCyan,
+            Color::White => Color::BrightWhite,
+            other => other,
+        }
+    }
+
+    /// Returns the normal-intensity twin of this color, mapping `BrightRed` to
+    /// `Red` and so on. Colors that are already normal intensity are returned
+    /// unchanged, as are `Fixed`, `RGB`, `Default`, and `Unset`.
+    pub fn to_non_bright(self) -> Color {
+        match self {
+            Color::BrightBlack => Color::Black,
+            Color::BrightRed => Color::Red,
+            Color::BrightGreen => Color::Green,
+            Color::BrightYellow => Color::Yellow,
+            Color::BrightBlue => Color::Blue,
+            Color::BrightMagenta => Color::Magenta,
+            Color::BrightCyan => Color::Cyan,
+            Color::BrightWhite => Color::White,
+            other => other,
+        }
+    }
+
     pub(crate) fn ansi_fmt(&self, f: &mut dyn fmt::Write, is_background: bool) -> fmt::Result {
         match (is_background, self.is_bright()) {
             (true, true) => write!(f, "10"),
@@ -150,3 +291,360 @@ impl Default for Color {
         Color::Unset
     }
 }
+
+impl<T: fmt::Display> Paint<T> {
+    /// Renders `item` as a run of text whose foreground color is linearly
+    /// interpolated from `start` to `end`, emitting one SGR escape per
+    /// `char` and a single reset at the end. Non-`RGB` endpoints are first
+    /// promoted to their canonical RGB values. See [`Color::gradient`].
+    ///
+    /// Coloring is applied per Unicode scalar value, not per grapheme cluster,
+    /// so a combining mark or ZWJ sequence may be split across escapes.
+    ///
+    /// ```rust
+    /// use yansi::{Color, Paint};
+    ///
+    /// println!("{}", Paint::gradient("sunrise", Color::RGB(255, 128, 0), Color::RGB(255, 0, 128)));
+    /// ```
+    pub fn gradient(item: T, start: Color, end: Color) -> String {
+        let chars: Vec<char> = item.to_string().chars().collect();
+        render(&chars, &Color::gradient(start, end, chars.len()))
+    }
+
+    /// Renders `item` as a run of text that cycles through the color spectrum,
+    /// emitting one SGR escape per `char` and a single reset at the end.
+    /// Coloring is per Unicode scalar value, not per grapheme cluster.
+    ///
+    /// ```rust
+    /// use yansi::Paint;
+    ///
+    /// println!("{}", Paint::rainbow("party!"));
+    /// ```
+    pub fn rainbow(item: T) -> String {
+        let chars: Vec<char> = item.to_string().chars().collect();
+        render(&chars, &rainbow_colors(chars.len()))
+    }
+}
+
+/// Emits each `char` in `chars` wrapped in the SGR escape for its paired
+/// color, finishing with a single reset. `colors` is expected to be the same
+/// length as `chars`; any surplus of either is ignored.
+fn render(chars: &[char], colors: &[Color]) -> String {
+    let mut out = String::new();
+    for (ch, color) in chars.iter().zip(colors) {
+        out.push_str("\u{1b}[");
+        let _ = color.ansi_fmt(&mut out, false);
+        out.push('m');
+        out.push(*ch);
+    }
+
+    if !chars.is_empty() {
+        out.push_str("\u{1b}[0m");
+    }
+
+    out
+}
+
+/// Builds a spectrum of `len` `RGB` colors by sweeping the hue from 0 to 360.
+fn rainbow_colors(len: usize) -> Vec<Color> {
+    (0..len)
+        .map(|i| {
+            let hue = 360.0 * i as f32 / len as f32;
+            let (r, g, b) = hsv_to_rgb(hue, 1.0, 1.0);
+            Color::RGB(r, g, b)
+        })
+        .collect()
+}
+
+/// Converts an HSV color (hue in degrees, saturation and value in `0.0..=1.0`)
+/// to its 24-bit RGB representation.
+fn hsv_to_rgb(hue: f32, saturation: f32, value: f32) -> (u8, u8, u8) {
+    let c = value * saturation;
+    let h = hue / 60.0;
+    let x = c * (1.0 - (h % 2.0 - 1.0).abs());
+    let (r, g, b) = match h as u8 {
+        0 => (c, x, 0.0),
+        1 => (x, c, 0.0),
+        2 => (0.0, c, x),
+        3 => (0.0, x, c),
+        4 => (x, 0.0, c),
+        _ => (c, 0.0, x),
+    };
+
+    let m = value - c;
+    let channel = |v: f32| ((v + m) * 255.0).round() as u8;
+    (channel(r), channel(g), channel(b))
+}
+
+/// The set of color capabilities a terminal may advertise.
+///
+/// Used with [`Color::downgrade`] to quantize a color down to what the
+/// terminal can actually render.
+#[derive(Debug, Eq, PartialEq, Ord, PartialOrd, Hash, Copy, Clone)]
+pub enum ColorLevel {
+    /// Only the 16 basic ANSI colors are understood.
+    Sixteen,
+
+    /// The 256-color extended palette is understood.
+    TwoFiftySix,
+
+    /// Full 24-bit RGB ("true color") is understood.
+    TrueColor,
+}
+
+/// An error produced when a string cannot be parsed into a [`Color`].
+#[derive(Debug, Eq, PartialEq, Ord, PartialOrd, Hash, Clone)]
+pub enum ParseColorError {
+    /// The string did not name a known color or a recognized numeric form.
+    UnknownName(String),
+
+    /// A numeric component was outside the valid `0`..=`255` range.
+    OutOfRange(String),
+
+    /// The string looked like an RGB triple or hex color but was malformed.
+    InvalidFormat(String),
+}
+
+impl fmt::Display for ParseColorError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            ParseColorError::UnknownName(ref s) => write!(f, "unknown color name: {}", s),
+            ParseColorError::OutOfRange(ref s) => write!(f, "color value out of range: {}", s),
+            ParseColorError::InvalidFormat(ref s) => write!(f, "invalid color format: {}", s),
+        }
+    }
+}
+
+impl Error for ParseColorError {}
+
+impl FromStr for Color {
+    type Err = ParseColorError;
+
+    fn from_str(s: &str) -> Result<Color, ParseColorError> {
+        let trimmed = s.trim();
+        let name = trimmed.to_lowercase().replace('_', "-");
+        let color = match &*name {
+            "unset" => Color::Unset,
+            "default" => Color::Default,
+            "black" => Color::Black,
+            "red" => Color::Red,
+            "green" => Color::Green,
+            "yellow" => Color::Yellow,
+            "blue" => Color::Blue,
+            "magenta" => Color::Magenta,
+            "cyan" => Color::Cyan,
+            "white" => Color::White,
+            "bright-black" => Color::BrightBlack,
+            "bright-red" => Color::BrightRed,
+            "bright-green" => Color::BrightGreen,
+            "bright-yellow" => Color::BrightYellow,
+            "bright-blue" => Color::BrightBlue,
+            "bright-magenta" => Color::BrightMagenta,
+            "bright-cyan" => Color::BrightCyan,
+            "bright-white" => Color::BrightWhite,
+            _ => return parse_numeric(trimmed),
+        };
+
+        Ok(color)
+    }
+}
+
+/// Parses the numeric color forms: a bare `Fixed` index, an `#rrggbb` hex
+/// string, or a comma/space separated `RGB` triple.
+fn parse_numeric(s: &str) -> Result<Color, ParseColorError> {
+    if let Some(hex) = s.strip_prefix('#') {
+        if hex.len() != 6 || !hex.is_ascii() {
+            return Err(ParseColorError::InvalidFormat(s.to_string()));
+        }
+
+        let channel = |range: std::ops::Range<usize>| {
+            u8::from_str_radix(&hex[range], 16)
+                .map_err(|_| ParseColorError::InvalidFormat(s.to_string()))
+        };
+
+        return Ok(Color::RGB(channel(0..2)?, channel(2..4)?, channel(4..6)?));
+    }
+
+    let parts: Vec<&str> = s
+        .split(|c| c == ',' || c == ' ')
+        .filter(|p| !p.is_empty())
+        .collect();
+
+    match parts.len() {
+        1 => parts[0]
+            .parse::<u8>()
+            .map(Color::Fixed)
+            .map_err(|_| out_of_range_or_unknown(s)),
+        3 => {
+            let channel = |p: &str| {
+                p.parse::<u8>().map_err(|_| {
+                    if p.chars().all(|c| c.is_ascii_digit()) {
+                        ParseColorError::OutOfRange(s.to_string())
+                    } else {
+                        ParseColorError::InvalidFormat(s.to_string())
+                    }
+                })
+            };
+
+            Ok(Color::RGB(channel(parts[0])?, channel(parts[1])?, channel(parts[2])?))
+        }
+        _ => Err(ParseColorError::InvalidFormat(s.to_string())),
+    }
+}
+
+/// A bare token that failed to parse as a `u8` is reported as out-of-range when
+/// it is all digits, and as an unknown name otherwise.
+fn out_of_range_or_unknown(s: &str) -> ParseColorError {
+    if s.chars().all(|c| c.is_ascii_digit()) {
+        ParseColorError::OutOfRange(s.to_string())
+    } else {
+        ParseColorError::UnknownName(s.to_string())
+    }
+}
+
+/// Overriding level forced by [`ColorLevel::set`], or `0` when detection is
+/// left to the environment. Encoded with [`ColorLevel::as_repr`].
+static FORCED: AtomicUsize = AtomicUsize::new(0);
+
+impl ColorLevel {
+    /// Detects the color support of the current terminal from the environment.
+    ///
+    /// `COLORTERM` is consulted first: a value of `truecolor` or `24bit` means
+    /// [`TrueColor`](ColorLevel::TrueColor). Otherwise `TERM` is inspected, with
+    /// a `*-256color` terminal reported as [`TwoFiftySix`](ColorLevel::TwoFiftySix)
+    /// and anything else falling back to [`Sixteen`](ColorLevel::Sixteen). The
+    /// environment is read at most once; the result is cached for the lifetime of
+    /// the process. A level forced with [`set`](ColorLevel::set) takes precedence
+    /// and bypasses the cache.
+    pub fn detect() -> ColorLevel {
+        if let Some(level) = ColorLevel::from_repr(FORCED.load(Ordering::Relaxed)) {
+            return level;
+        }
+
+        static DETECT: Once = Once::new();
+        static CACHE: AtomicUsize = AtomicUsize::new(0);
+        DETECT.call_once(|| {
+            CACHE.store(ColorLevel::from_env().as_repr(), Ordering::Relaxed);
+        });
+
+        ColorLevel::from_repr(CACHE.load(Ordering::Relaxed)).unwrap_or(ColorLevel::Sixteen)
+    }
+
+    /// Forces [`detect`](ColorLevel::detect) to report `level`, overriding the
+    /// environment. Useful for tests or when piping to a known consumer.
+    pub fn set(level: ColorLevel) {
+        FORCED.store(level.as_repr(), Ordering::Relaxed);
+    }
+
+    /// Clears a level previously forced with [`set`](ColorLevel::set), restoring
+    /// environment-based detection.
+    pub fn unset() {
+        FORCED.store(0, Ordering::Relaxed);
+    }
+
+    fn from_env() -> ColorLevel {
+        if let Ok(colorterm) = env::var("COLORTERM") {
+            if colorterm == "truecolor" || colorterm == "24bit" {
+                return ColorLevel::TrueColor;
+            }
+        }
+
+        match env::var("TERM") {
+            Ok(ref term) if term.ends_with("-256color") => ColorLevel::TwoFiftySix,
+            _ => ColorLevel::Sixteen,
+        }
+    }
+
+    #[inline]
+    fn as_repr(self) -> usize {
+        match self {
+            ColorLevel::Sixteen => 1,
+            ColorLevel::TwoFiftySix => 2,
+            ColorLevel::TrueColor => 3,
+        }
+    }
+
+    #[inline]
+    fn from_repr(repr: usize) -> Option<ColorLevel> {
+        match repr {
+            1 => Some(ColorLevel::Sixteen),
+            2 => Some(ColorLevel::TwoFiftySix),
+            3 => Some(ColorLevel::TrueColor),
+            _ => None,
+        }
+    }
+}
+
+/// Canonical RGB values for the 16 basic ANSI colors, paired with the `Color`
+/// they map to. The ordering matches the ANSI numbering (standard 0-7 then
+/// bright 0-7).
+static PALETTE_16: [(Color, (u8, u8, u8)); 16] = [
+    (Color::Black, (0, 0, 0)),
+    (Color::Red, (170, 0, 0)),
+    (Color::Green, (0, 170, 0)),
+    (Color::Yellow, (170, 85, 0)),
+    (Color::Blue, (0, 0, 170)),
+    (Color::Magenta, (170, 0, 170)),
+    (Color::Cyan, (0, 170, 170)),
+    (Color::White, (170, 170, 170)),
+    (Color::BrightBlack, (85, 85, 85)),
+    (Color::BrightRed, (255, 85, 85)),
+    (Color::BrightGreen, (85, 255, 85)),
+    (Color::BrightYellow, (255, 255, 85)),
+    (Color::BrightBlue, (85, 85, 255)),
+    (Color::BrightMagenta, (255, 85, 255)),
+    (Color::BrightCyan, (85, 255, 255)),
+    (Color::BrightWhite, (255, 255, 255)),
+];
+
+/// Floors a single 0-255 channel to its 0-5 coordinate in the 6x6x6 color cube.
+#[inline]
+fn cube_coord(channel: u8) -> u8 {
+    (channel as f32 / 255.0 * 5.0) as u8
+}
+
+/// Quantizes a 24-bit RGB color to an index in the 256-color palette: grays
+/// map onto the grayscale ramp, other colors onto the 6x6x6 cube with each
+/// channel floored, which is not always the nearest index.
+fn rgb_to_256(r: u8, g: u8, b: u8) -> u8 {
+    if r == g && g == b {
+        if r < 8 {
+            16
+        } else if r > 248 {
+            231
+        } else {
+            ((r as f32 - 8.0) / 247.0 * 24.0) as u8 + 232
+        }
+    } else {
+        16 + 36 * cube_coord(r) + 6 * cube_coord(g) + cube_coord(b)
+    }
+}
+
+/// Resolves a 256-palette index to its canonical 24-bit RGB value.
+fn fixed_to_rgb(n: u8) -> (u8, u8, u8) {
+    if n < 16 {
+        PALETTE_16[n as usize].1
+    } else if n < 232 {
+        let n = n - 16;
+        let level = |c: u8| if c == 0 { 0 } else { 55 + c * 40 };
+        (level(n / 36), level((n / 6) % 6), level(n % 6))
+    } else {
+        let v = 8 + (n - 232) * 10;
+        (v, v, v)
+    }
+}
+
+/// Picks the basic ANSI color whose canonical RGB is closest to `(r, g, b)` by
+/// squared euclidean distance.
+fn rgb_to_16(r: u8, g: u8, b: u8) -> Color {
+    PALETTE_16
+        .iter()
+        .min_by_key(|&&(_, (pr, pg, pb))| {
+            let dr = pr as i32 - r as i32;
+            let dg = pg as i32 - g as i32;
+            let db = pb as i32 - b as i32;
+            dr * dr + dg * dg + db * db
+        })
+        .map(|&(color, _)| color)
+        .unwrap()
+}
@@ -0,0 +1,3 @@
+mod color;
+
+pub use color::{Color, ColorLevel, ParseColorError};